@@ -1,5 +1,7 @@
 use std::time::SystemTime;
 
+use rayon::prelude::*;
+
 //----------------------------------------------------------------------------//
 
 fn the_naive_one(mut k: usize) -> usize {
@@ -30,7 +32,7 @@ fn count_factors(n: usize) -> usize {
    // We can use a trick here; all the divisors after the square root of N will
    // be mirrored, so we just count them twice. For example, 16: 2x8, 4x4, 8x2
    for i in 2..=(n as f64).sqrt() as usize {
-      if n % i == 0 {
+      if n.is_multiple_of(i) {
          if i != n / i { count += 2; }
          else          { count += 1; }
       }       
@@ -85,7 +87,7 @@ fn the_fast_one(mut k: usize) -> usize {
 fn find_exponent(mut n: usize, factor: usize) -> usize {
    let mut exponent = 0;
 
-   while n % factor == 0 {
+   while n.is_multiple_of(factor) {
       n /= factor;
       exponent += 1;
    }
@@ -122,10 +124,26 @@ a number system with a finite base, as it will break once it reaches that base.
 Regardless, since this is concatenation it's very easily parallelizable.
 The current code does not go that far, but the structure is there.
 */
-fn the_faster_one(mut k: usize) -> usize {
-   k += 1;
+fn the_faster_one(k: usize) -> usize {
+   let factors = build_factors_faster(k);
 
    let mut count = 0;
+
+   for n in 2..factors.len() {
+      if factors[n] == factors[n - 1] {
+         count += 1;
+      }
+   }
+
+   count
+}
+
+// Builds the factors[] array that `the_faster_one` counts over, shared with
+// every consumer that needs the raw per-number divisor counts instead of
+// just the final tally (term collection, b-file export, etc).
+fn build_factors_faster(mut k: usize) -> Vec<usize> {
+   k += 1;
+
    let mut factors = vec!(1; k + 1);
 
    for n in 2..=k {
@@ -149,20 +167,440 @@ fn the_faster_one(mut k: usize) -> usize {
                let until = j + (n - 1)*step;
 
                for k in (j..until.min(k + 1)).step_by(step) {
-                  factors[k] *= i + 1;
+                  factors[k] *= (i + 1) as usize;
                }
             }
          }
       }
+   }
 
-      if factors[n] == factors[n - 1] {
+   factors
+}
+
+// Same sieve as `the_faster_one`, but instead of tallying matches it collects
+// the actual A005237 members so they can be audited against the published
+// sequence or exported with `write_b_file`.
+fn the_faster_one_terms(k: usize) -> Vec<usize> {
+   count_with_indices(k, 2, |_, w| w[0] == w[1])
+}
+
+// Serializes `terms` in OEIS b-file format, i.e. one "index value" pair per
+// line with a 1-based index, so a run can be diffed straight against the
+// b-file published on the OEIS page for the sequence.
+fn write_b_file(path: &str, terms: &[usize]) -> std::io::Result<()> {
+   use std::io::Write;
+
+   let mut file = std::fs::File::create(path)?;
+
+   for (i, term) in terms.iter().enumerate() {
+      writeln!(file, "{0} {1}", i + 1, term)?;
+   }
+
+   Ok(())
+}
+
+//----------------------------------------------------------------------------//
+
+/*
+`the_faster_one` hardcodes the A005237 criterion, factors[n] == factors[n - 1],
+but the sieve that builds `factors[]` doesn't care what happens to it
+afterwards. Generalizing the comparison to a predicate over a sliding window
+of `window` consecutive divisor counts turns this one sieve pass into a whole
+family of sequences, for example:
+
+   window 2, |_, w| w[0] == w[1]             -> A005237
+   window L, |_, w| w.iter().all(|&c| c == w[0])  -> A006558-style runs of L
+   window 2, |_, w| w[1] > w[0]              -> positions where d(n) increases
+   window 1, |n, w| n % w[0] == 0            -> refactorable numbers, A033950
+
+The predicate is given the window's starting number `n` together with the
+divisor counts, since criteria like refactorable numbers need `n` itself, not
+just its divisor count.
+*/
+fn count_with<F>(k: usize, window: usize, pred: F) -> usize
+   where F: Fn(usize, &[usize]) -> bool
+{
+   let factors = build_factors_faster(k);
+
+   let mut count = 0;
+
+   for n in 1..=k.min(factors.len().saturating_sub(window)) {
+      if pred(n, &factors[n..n + window]) {
+         count += 1;
+      }
+   }
+
+   count
+}
+
+// Same as `count_with`, but returns the matching starting indices instead of
+// just the tally (the count is just `indices.len()`).
+fn count_with_indices<F>(k: usize, window: usize, pred: F) -> Vec<usize>
+   where F: Fn(usize, &[usize]) -> bool
+{
+   let factors = build_factors_faster(k);
+
+   let mut indices = vec!();
+
+   for n in 1..=k.min(factors.len().saturating_sub(window)) {
+      if pred(n, &factors[n..n + window]) {
+         indices.push(n);
+      }
+   }
+
+   indices
+}
+
+//----------------------------------------------------------------------------//
+
+/*
+the_faster_one's exponent concatenation is independent per prime, but the
+single shared `factors` array still forces the whole sieve to run serially.
+Chopping [2, k] into fixed-size blocks removes that dependency: each block
+only needs the primes up to sqrt(hi) and a small local buffer, so the blocks
+carry no shared state and can be handed to Rayon.
+*/
+fn the_parallel_one(mut k: usize) -> usize {
+   k += 1;
+
+   const BLOCK_SIZE: usize = 1 << 16;
+
+   let primes = sieve_primes((k as f64).sqrt() as usize + 1);
+   let blocks: Vec<usize> = (2..=k).step_by(BLOCK_SIZE).collect();
+
+   blocks
+      .into_par_iter()
+      .map(|lo| count_block(lo, (lo + BLOCK_SIZE - 1).min(k), &primes))
+      .sum()
+}
+
+// Computes the exact divisor count of every integer in [lo, hi] using trial
+// division by the precomputed primes, then counts how many of them match the
+// divisor count of their predecessor. The block's own predecessor (lo - 1)
+// is recomputed on the spot so matches are not lost at the block boundary.
+fn count_block(lo: usize, hi: usize, primes: &[usize]) -> usize {
+   let len = hi - lo + 1;
+   let mut rem: Vec<usize> = (lo..=hi).collect();
+   let mut cnt = vec!(1; len);
+
+   for &p in primes {
+      if p * p > hi {
+         break;
+      }
+
+      let start = lo.div_ceil(p) * p;
+
+      for m in (start..=hi).step_by(p) {
+         let i = m - lo;
+         let mut exponent = 0;
+
+         while rem[i].is_multiple_of(p) {
+            rem[i] /= p;
+            exponent += 1;
+         }
+
+         cnt[i] *= exponent + 1;
+      }
+   }
+
+   for (c, &r) in cnt.iter_mut().zip(&rem) {
+      if r > 1 {
+         *c *= 2;
+      }
+   }
+
+   // d(1) is conventionally 1 here, matching the seed used by the other
+   // algorithms; every other predecessor is recomputed by trial division.
+   let mut last = if lo == 2 { 1 } else { count_factors(lo - 1) };
+   let mut count = 0;
+
+   for &c in &cnt {
+      if c == last {
          count += 1;
       }
+
+      last = c;
    }
 
    count
 }
 
+// A plain sieve of Eratosthenes used to precompute the primes up to sqrt(k)
+// once, up front, so every block can reuse the same list.
+fn sieve_primes(n: usize) -> Vec<usize> {
+   if n < 2 {
+      return vec!();
+   }
+
+   let mut is_prime = vec!(true; n + 1);
+   is_prime[0] = false;
+   is_prime[1] = false;
+
+   for i in 2..=n {
+      if is_prime[i] {
+         for j in (i * i..=n).step_by(i) {
+            is_prime[j] = false;
+         }
+      }
+   }
+
+   (2..=n).filter(|&i| is_prime[i]).collect()
+}
+
+//----------------------------------------------------------------------------//
+
+/*
+count_factors only works by trial division up to sqrt(n), which is hopeless
+for a single 64-bit number with a large prime factor. Pollard's rho splits
+such a number into smaller factors in roughly O(n^(1/4)) time, and pairing it
+with a deterministic Miller-Rabin primality test lets us recognize when a
+part is already prime, so the same product-of-(exponent + 1) formula applies
+without ever trial dividing past small primes.
+*/
+fn count_factors_big(mut n: u64) -> u64 {
+   if n < 2 {
+      return 0;
+   }
+
+   let mut factors = vec!();
+
+   let mut exponent = 0;
+
+   while n.is_multiple_of(2) {
+      n /= 2;
+      exponent += 1;
+   }
+
+   if exponent > 0 {
+      factors.push((2, exponent));
+   }
+
+   if n > 1 {
+      factorize_big(n, &mut factors);
+   }
+
+   factors.iter().fold(1, |count, &(_, exponent)| count * (exponent + 1))
+}
+
+// Recursively splits `n` with Pollard's rho, bottoming out on parts that
+// Miller-Rabin recognizes as prime.
+fn factorize_big(n: u64, factors: &mut Vec<(u64, u64)>) {
+   if n == 1 {
+      return;
+   }
+
+   if is_prime_mr(n) {
+      for pair in factors.iter_mut() {
+         if pair.0 == n {
+            pair.1 += 1;
+            return;
+         }
+      }
+
+      factors.push((n, 1));
+      return;
+   }
+
+   let d = pollard_rho(n);
+
+   factorize_big(d, factors);
+   factorize_big(n / d, factors);
+}
+
+// Pollard's rho with Floyd cycle detection: f(x) = x² + c mod n. Each tick
+// advances a "tortoise" x by one step of f and a "hare" y by two, and gcd(|x
+// - y|, n) eventually lands on a nontrivial factor. If it lands on n itself,
+// the cycle closed before splitting anything, so retry with a different c.
+fn pollard_rho(n: u64) -> u64 {
+   if n.is_multiple_of(2) {
+      return 2;
+   }
+
+   let mut c: u64 = 1;
+
+   loop {
+      let f = |x: u64| (mulmod(x, x, n) + c) % n;
+
+      let mut x = 2;
+      let mut y = 2;
+      let mut d = 1;
+
+      while d == 1 {
+         x = f(x);
+         y = f(f(y));
+         d = gcd(x.abs_diff(y), n);
+      }
+
+      if d != n {
+         return d;
+      }
+
+      c += 1;
+   }
+}
+
+fn gcd(mut a: u64, mut b: u64) -> u64 {
+   while b != 0 {
+      let t = b;
+      b = a % b;
+      a = t;
+   }
+
+   a
+}
+
+// Deterministic Miller-Rabin for u64: the witness set {2, 3, 5, 7, 11, 13,
+// 17, 19, 23, 29, 31, 37} is proven to correctly classify every number below
+// 2^64, so there's no probabilistic error to worry about.
+fn is_prime_mr(n: u64) -> bool {
+   const WITNESSES: [u64; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+
+   if n < 2 {
+      return false;
+   }
+
+   for &p in &WITNESSES {
+      if n == p {
+         return true;
+      }
+
+      if n.is_multiple_of(p) {
+         return false;
+      }
+   }
+
+   let mut d = n - 1;
+   let mut s = 0;
+
+   while d.is_multiple_of(2) {
+      d /= 2;
+      s += 1;
+   }
+
+   'witness: for &a in &WITNESSES {
+      let mut x = powmod(a, d, n);
+
+      if x == 1 || x == n - 1 {
+         continue;
+      }
+
+      for _ in 0..s - 1 {
+         x = mulmod(x, x, n);
+
+         if x == n - 1 {
+            continue 'witness;
+         }
+      }
+
+      return false;
+   }
+
+   true
+}
+
+fn mulmod(a: u64, b: u64, m: u64) -> u64 {
+   ((a as u128 * b as u128) % m as u128) as u64
+}
+
+fn powmod(mut base: u64, mut exponent: u64, modulus: u64) -> u64 {
+   let mut result = 1;
+
+   base %= modulus;
+
+   while exponent > 0 {
+      if exponent & 1 == 1 {
+         result = mulmod(result, base, modulus);
+      }
+
+      base = mulmod(base, base, modulus);
+      exponent >>= 1;
+   }
+
+   result
+}
+
+//----------------------------------------------------------------------------//
+
+/*
+T(N) = Σ spf(n) for n in [2, N], the sum of each integer's smallest prime
+factor, shares this crate's theme of factor-driven sieving. A linear (Euler)
+sieve computes every spf[i] in O(N) total work: when spf[i] is still 0, i is
+prime, so spf[i] = i and it joins the growing prime list; either way, i is
+then combined with each already-found prime p, in ascending order, marking
+spf[i * p] = p and stopping as soon as p divides i, which guarantees every
+composite gets marked exactly once, by its own least prime factor.
+*/
+fn sum_smallest_prime_factor(n: usize) -> u128 {
+   let mut spf = vec!(0; n + 1);
+   let mut primes = vec!();
+   let mut sum: u128 = 0;
+
+   for i in 2..=n {
+      if spf[i] == 0 {
+         spf[i] = i;
+         primes.push(i);
+      }
+
+      sum += spf[i] as u128;
+
+      for &p in &primes {
+         if i * p > n {
+            break;
+         }
+
+         spf[i * p] = p;
+
+         if i % p == 0 {
+            break;
+         }
+      }
+   }
+
+   sum
+}
+
+// Segmented variant of the same sum: the full O(N) spf[] array is memory-
+// bound for very large N, but each block only needs the primes up to
+// sqrt(N), so T(N) can be pushed much higher within a fixed memory budget.
+fn sum_smallest_prime_factor_segmented(n: usize) -> u128 {
+   const BLOCK_SIZE: usize = 1 << 16;
+
+   let primes = sieve_primes((n as f64).sqrt() as usize + 1);
+
+   let mut sum: u128 = 0;
+   let mut lo = 2;
+
+   while lo <= n {
+      let hi = (lo + BLOCK_SIZE - 1).min(n);
+      let len = hi - lo + 1;
+      let mut spf = vec!(0; len);
+
+      for &p in &primes {
+         if p * p > hi {
+            break;
+         }
+
+         let start = lo.div_ceil(p) * p;
+
+         for m in (start..=hi).step_by(p) {
+            let i = m - lo;
+
+            if spf[i] == 0 {
+               spf[i] = p;
+            }
+         }
+      }
+
+      for (i, &s) in spf.iter().enumerate() {
+         sum += if s != 0 { s } else { lo + i } as u128;
+      }
+
+      lo = hi + 1;
+   }
+
+   sum
+}
+
 //----------------------------------------------------------------------------//
 
 fn main() {
@@ -171,9 +609,19 @@ fn main() {
       let a = the_naive_one(n);
       let b = the_fast_one(n);
       let c = the_faster_one(n);
+      let d = the_parallel_one(n);
+      let e = count_with(n, 2, |_, w| w[0] == w[1]);
+      let h = count_with_indices(n, 2, |_, w| w[0] == w[1]).len();
 
-      if !(a == b && b == c) {
-         println!("algorithms dont match: {0} {1} {2} at {3}", a, b, c, n);
+      if !(a == b && b == c && c == d && d == e && e == h) {
+         println!("algorithms dont match: {0} {1} {2} {3} {4} {5} at {6}", a, b, c, d, e, h, n);
+      }
+
+      let f = count_factors(n) as u64;
+      let g = count_factors_big(n as u64);
+
+      if f != g {
+         println!("count_factors_big disagrees at {0}: {1} vs {2}", n, f, g);
       }
    }
 
@@ -192,4 +640,41 @@ fn main() {
 
    println!("{0}", the_faster_one(x));
    println!("the faster one took {0}ms", t.elapsed().unwrap().as_millis());
+
+   let t = SystemTime::now();
+
+   println!("{0}", the_parallel_one(x));
+   println!("the parallel one took {0}ms", t.elapsed().unwrap().as_millis());
+
+   let terms = the_faster_one_terms(x);
+
+   write_b_file("b005237.txt", &terms).unwrap();
+   println!("wrote {0} terms to b005237.txt", terms.len());
+
+   let big = 1_000_000_000_000_000_003;
+   let t = SystemTime::now();
+
+   println!("{0}", count_factors_big(big));
+   println!("count_factors_big took {0}ms", t.elapsed().unwrap().as_millis());
+
+   let t = SystemTime::now();
+   let spf_sum = sum_smallest_prime_factor(x);
+
+   println!("{0}", spf_sum);
+   println!("sum_smallest_prime_factor took {0}ms", t.elapsed().unwrap().as_millis());
+
+   let t = SystemTime::now();
+   let spf_sum_segmented = sum_smallest_prime_factor_segmented(x);
+
+   println!("{0}", spf_sum_segmented);
+   println!("sum_smallest_prime_factor_segmented took {0}ms", t.elapsed().unwrap().as_millis());
+
+   if spf_sum != spf_sum_segmented {
+      println!("spf sums disagree: {0} vs {1}", spf_sum, spf_sum_segmented);
+   }
+
+   // Refactorable numbers (A033950): n whose divisor count divides n.
+   let refactorable = count_with_indices(1000, 1, |n, w| n.is_multiple_of(w[0]));
+
+   println!("{0} refactorable numbers up to 1000", refactorable.len());
 }